@@ -0,0 +1,18 @@
+// Copyright (C) 2025-2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! A crate for formatted writing into uninitialized stack allocated
+//! memory.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(all(feature = "nightly", feature = "std"), feature(can_vector, read_buf, core_io_borrowed_buf))]
+
+#[cfg(feature = "std")]
+mod line_writer;
+mod writer;
+
+#[cfg(feature = "std")]
+pub use crate::line_writer::LineWriter;
+pub use crate::writer::Overflow;
+pub use crate::writer::Overflowed;
+pub use crate::writer::Writer;
@@ -0,0 +1,155 @@
+// Copyright (C) 2025-2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::cmp::min;
+use std::io;
+use std::io::Write;
+use std::mem::MaybeUninit;
+
+use crate::Writer;
+
+
+/// A line-buffered adapter that stages writes in an uninitialized stack
+/// buffer before flushing them to an inner [`io::Write`].
+///
+/// Like [`std::io::LineWriter`] it batches many small writes into
+/// infrequent large ones against an expensive sink (for example a
+/// socket), but keeps the scratch space on the stack rather than on the
+/// heap — the very reason this crate exists. Buffered bytes are drained
+/// into the inner writer whenever a newline is seen or the buffer would
+/// overflow; [`flush`][io::Write::flush] pushes any residual bytes
+/// through.
+///
+/// ```rust
+/// # use std::io::Write as _;
+/// # use std::mem::MaybeUninit;
+/// let mut buffer = [MaybeUninit::<u8>::uninit(); 256];
+/// let mut writer = bufio::LineWriter::new(&mut buffer, Vec::new());
+/// write!(writer, "hello\n").unwrap();
+/// writer.flush().unwrap();
+/// assert_eq!(writer.into_inner(), b"hello\n");
+/// ```
+#[derive(Debug)]
+pub struct LineWriter<'buf, W> {
+  /// The stack buffer used to stage writes before flushing.
+  writer: Writer<'buf>,
+  /// The inner writer that buffered bytes are ultimately drained into.
+  inner: W,
+}
+
+impl<'buf, W> LineWriter<'buf, W>
+where
+  W: Write,
+{
+  /// Create a new [`LineWriter`] staging into `buffer` and flushing to
+  /// `inner`.
+  #[inline]
+  pub fn new(buffer: &'buf mut [MaybeUninit<u8>], inner: W) -> Self {
+    Self {
+      writer: Writer::new(buffer),
+      inner,
+    }
+  }
+
+  /// Unwrap this [`LineWriter`], returning the inner writer.
+  ///
+  /// Any bytes still buffered are discarded; call
+  /// [`flush`][io::Write::flush] first to retain them.
+  #[inline]
+  pub fn into_inner(self) -> W {
+    self.inner
+  }
+
+  /// Drain the staged bytes into the inner writer and reset the buffer.
+  fn drain(&mut self) -> io::Result<()> {
+    let () = self.inner.write_all(self.writer.written())?;
+    let () = self.writer.reset();
+    Ok(())
+  }
+}
+
+impl<W> Write for LineWriter<'_, W>
+where
+  W: Write,
+{
+  fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+    if self.writer.remaining() == 0 {
+      let () = self.drain()?;
+    }
+
+    let len = min(data.len(), self.writer.remaining());
+    let chunk = &data[..len];
+    let n = self.writer.write(chunk)?;
+
+    if chunk.contains(&b'\n') {
+      let () = self.drain()?;
+    }
+    Ok(n)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    let () = self.drain()?;
+    self.inner.flush()
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that lines are batched and flushed to the inner writer.
+  #[test]
+  fn line_buffered_flushing() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 64];
+    let mut writer = LineWriter::new(&mut buffer, Vec::new());
+
+    // A write without a newline stays buffered.
+    let n = writer.write(b"foo").unwrap();
+    assert_eq!(n, 3);
+    assert_eq!(writer.inner, b"");
+
+    // A newline flushes everything staged so far.
+    let n = writer.write(b"bar\n").unwrap();
+    assert_eq!(n, 4);
+    assert_eq!(writer.inner, b"foobar\n");
+
+    // The tail after a newline is buffered again until flushed.
+    let n = writer.write(b"baz").unwrap();
+    assert_eq!(n, 3);
+    assert_eq!(writer.inner, b"foobar\n");
+
+    let () = writer.flush().unwrap();
+    assert_eq!(writer.inner, b"foobar\nbaz");
+  }
+
+  /// Check that a full buffer is drained before accepting more bytes.
+  #[test]
+  fn overflow_flushing() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 4];
+    let mut writer = LineWriter::new(&mut buffer, Vec::new());
+
+    assert_eq!(writer.write(b"abcd").unwrap(), 4);
+    assert_eq!(writer.inner, b"");
+
+    // The next write finds no room and drains first.
+    assert_eq!(writer.write(b"ef").unwrap(), 2);
+    assert_eq!(writer.inner, b"abcd");
+
+    let () = writer.flush().unwrap();
+    assert_eq!(writer.into_inner(), b"abcdef");
+  }
+
+  /// Check that a newline anywhere in a single write triggers a flush.
+  #[test]
+  fn embedded_newline_flushing() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 64];
+    let mut writer = LineWriter::new(&mut buffer, Vec::new());
+
+    let n = writer.write(b"foo\nbar").unwrap();
+    assert_eq!(n, 7);
+    // The whole staged chunk is drained once the newline is seen.
+    assert_eq!(writer.inner, b"foo\nbar");
+  }
+}
@@ -1,19 +1,69 @@
 // Copyright (C) 2025-2026 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use std::cmp::min;
+use core::cmp::min;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+#[cfg(not(feature = "std"))]
+use core::fmt::Write as FmtWrite;
+use core::mem::MaybeUninit;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
 use std::io;
-use std::mem::MaybeUninit;
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+
+/// The action to take when a write would exceed the buffer's capacity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+  /// Cap the write at the remaining space, writing as many bytes as fit
+  /// and reporting only those as written.
+  #[default]
+  Truncate,
+  /// Fail the write with a distinct error as soon as it would exceed the
+  /// remaining capacity.
+  Error,
+}
+
+
+/// An error reported by a [`Writer`] in [`Overflow::Error`] mode when a
+/// write would exceed the buffer's remaining capacity.
+#[derive(Clone, Copy, Debug)]
+pub struct Overflowed {
+  /// The number of bytes that did not fit and were dropped.
+  pub dropped: usize,
+}
+
+impl Display for Overflowed {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    write!(
+      f,
+      "write exceeded buffer capacity; {} byte(s) dropped",
+      self.dropped
+    )
+  }
+}
 
+#[cfg(feature = "std")]
+impl Error for Overflowed {}
 
-/// A type implementing `io::Write` for a potentially uninitialized
-/// slice of memory.
+
+/// A type implementing `io::Write` (or, in `no_std` builds,
+/// `core::fmt::Write` instead) for a potentially uninitialized slice of
+/// memory.
 ///
 /// The explicit intent is to enable formatted writing to uninitialized
 /// stack allocated memory, which is "allocatable" with a mere increment
 /// of the stack pointer. E.g.,
 ///
 /// ```rust
+/// # #[cfg(feature = "std")] {
 /// # use std::io::stdout;
 /// # use std::io::Write as _;
 /// # use std::mem::MaybeUninit;
@@ -24,6 +74,7 @@ use std::mem::MaybeUninit;
 /// // `print!` or `write!`; this example is just for illustration
 /// // purposes.
 /// stdout().write(writer.written()).unwrap();
+/// # }
 /// ```
 #[derive(Debug)]
 pub struct Writer<'buf> {
@@ -31,6 +82,11 @@ pub struct Writer<'buf> {
   buffer: &'buf mut [MaybeUninit<u8>],
   /// The total number of bytes written to `buffer`.
   written: usize,
+  /// The action to take when a write would exceed `buffer`'s capacity.
+  overflow: Overflow,
+  /// Whether a write was ever truncated or rejected because it would
+  /// have exceeded `buffer`'s capacity.
+  overflowed: bool,
 }
 
 impl<'buf> Writer<'buf> {
@@ -38,7 +94,47 @@ impl<'buf> Writer<'buf> {
   /// to write to.
   #[inline]
   pub fn new(buffer: &'buf mut [MaybeUninit<u8>]) -> Self {
-    Self { buffer, written: 0 }
+    Self {
+      buffer,
+      written: 0,
+      overflow: Overflow::Truncate,
+      overflowed: false,
+    }
+  }
+
+  /// Create a new [`Writer`] that fails writes exceeding the buffer's
+  /// capacity instead of silently truncating them.
+  ///
+  /// This is shorthand for [`new`][Self::new] followed by
+  /// `set_overflow(`[`Overflow::Error`]`)`.
+  #[inline]
+  pub fn new_strict(buffer: &'buf mut [MaybeUninit<u8>]) -> Self {
+    let mut slf = Self::new(buffer);
+    let () = slf.set_overflow(Overflow::Error);
+    slf
+  }
+
+  /// Set the [`Overflow`] behavior used when a write would exceed the
+  /// buffer's remaining capacity.
+  #[inline]
+  pub fn set_overflow(&mut self, overflow: Overflow) {
+    self.overflow = overflow;
+  }
+
+  /// Retrieve the number of bytes that can still be written before the
+  /// buffer is full.
+  #[inline]
+  pub fn remaining(&self) -> usize {
+    self.buffer.len() - self.written
+  }
+
+  /// Check whether a write was ever truncated or rejected because it
+  /// would have exceeded the buffer's capacity.
+  ///
+  /// The flag is cleared by [`reset`][Self::reset].
+  #[inline]
+  pub fn overflowed(&self) -> bool {
+    self.overflowed
   }
 
   /// Retrieve the slice of the managed buffer that has been written so
@@ -53,26 +149,190 @@ impl<'buf> Writer<'buf> {
     unsafe { &*(slice as *const [MaybeUninit<u8>] as *const [u8]) }
   }
 
+  /// Copy as many bytes of `data` as fit into the uninitialized tail,
+  /// advancing `written` and flagging an overflow if not everything
+  /// fit. Returns the number of bytes actually copied.
+  #[inline]
+  fn put(&mut self, data: &[u8]) -> usize {
+    let len = min(data.len(), self.buffer.len() - self.written);
+    if len < data.len() {
+      self.overflowed = true;
+    }
+
+    let ptr = self.buffer[self.written..].as_mut_ptr().cast::<u8>();
+    // SAFETY: Both source and destination are valid for reads and are
+    //         properly aligned as they originate from references. They
+    //         cannot overlap because this method has exclusive access
+    //         to the buffer we write to.
+    let () = unsafe { ptr.copy_from_nonoverlapping(data.as_ptr(), len) };
+
+    self.written += len;
+    len
+  }
+
+  /// Fill the remaining buffer space by reading directly from `reader`.
+  ///
+  /// Bytes are read into the still-uninitialized tail
+  /// `buffer[written..]` until the buffer is full or `reader` reaches
+  /// end-of-file, advancing the written region by exactly as many bytes
+  /// as the reader reports. [`ErrorKind::Interrupted`][io::ErrorKind::Interrupted]
+  /// is retried transparently; the total number of bytes copied is
+  /// returned.
+  ///
+  /// Modeled after [`std::io::copy`], this splices a reader's bytes into
+  /// the same stack buffer used for formatting. With the `nightly`
+  /// feature the uninitialized tail is handed to the reader via a
+  /// [`BorrowedBuf`][std::io::BorrowedBuf], so only the bytes actually
+  /// read are ever marked initialized; on stable the target region is
+  /// zeroed once before the first [`read`][Read::read].
+  #[cfg(feature = "std")]
+  pub fn fill_from<R>(&mut self, reader: &mut R) -> io::Result<usize>
+  where
+    R: Read,
+  {
+    let start = self.written;
+
+    #[cfg(not(feature = "nightly"))]
+    {
+      // Without `BorrowedBuf` we cannot hand uninitialized memory to a
+      // `Read`, so zero the tail once up front and then treat it as an
+      // initialized slice.
+      let ptr = self.buffer[self.written..].as_mut_ptr().cast::<u8>();
+      let len = self.buffer.len() - self.written;
+      // SAFETY: The tail is valid for writes of `len` bytes and `u8`
+      //         has no invalid bit patterns.
+      let () = unsafe { ptr.write_bytes(0, len) };
+      // SAFETY: We just initialized the tail to zero.
+      let tail = unsafe { core::slice::from_raw_parts_mut(ptr, len) };
+
+      loop {
+        if self.written == self.buffer.len() {
+          break
+        }
+        let dst = &mut tail[self.written - start..];
+        match reader.read(dst) {
+          Ok(0) => break,
+          Ok(n) => self.written += n,
+          Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+          Err(e) => return Err(e),
+        }
+      }
+    }
+
+    #[cfg(feature = "nightly")]
+    {
+      use std::io::BorrowedBuf;
+
+      loop {
+        if self.written == self.buffer.len() {
+          break
+        }
+        let mut buf = BorrowedBuf::from(&mut self.buffer[self.written..]);
+        let mut cursor = buf.unfilled();
+        match reader.read_buf(cursor.reborrow()) {
+          Ok(()) => {
+            let n = cursor.written();
+            if n == 0 {
+              break
+            }
+            self.written += n;
+          }
+          Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+          Err(e) => return Err(e),
+        }
+      }
+    }
+
+    Ok(self.written - start)
+  }
+
+  /// Retrieve the still-unwritten region of the buffer as a slice of
+  /// uninitialized bytes.
+  ///
+  /// This mirrors [`BufWriter::spare_capacity_mut`][std::io::BufWriter::spare_capacity_mut]
+  /// and lets a producer — such as a serializer or a [`Read`] that
+  /// knows how many bytes it wrote — fill the raw tail directly. Use
+  /// [`advance`][Self::advance] afterwards to commit the written bytes.
+  #[inline]
+  pub fn spare_capacity(&mut self) -> &mut [MaybeUninit<u8>] {
+    &mut self.buffer[self.written..]
+  }
+
+  /// Mark the first `n` bytes of the [spare capacity][Self::spare_capacity]
+  /// as written.
+  ///
+  /// # Safety
+  /// The caller must ensure that the first `n` bytes of the slice
+  /// previously returned by [`spare_capacity`][Self::spare_capacity]
+  /// have been initialized and that `n` does not exceed
+  /// [`remaining`][Self::remaining].
+  #[inline]
+  pub unsafe fn advance(&mut self, n: usize) {
+    debug_assert!(n <= self.remaining());
+    self.written += n;
+  }
+
   /// Reset the buffer to its "empty" state.
   #[inline]
   pub fn reset(&mut self) {
     self.written = 0;
+    self.overflowed = false;
+  }
+}
+
+/// A [`core::fmt::Write`] implementation so that `write!` works without
+/// `std` — overflowing the buffer surfaces as a `core::fmt::Error`.
+///
+/// Only provided in `no_std` builds; when the `std` feature is on
+/// [`io::Write`] already supplies `write_fmt` and a second `write_fmt`
+/// would make `write!` ambiguous.
+#[cfg(not(feature = "std"))]
+impl FmtWrite for Writer<'_> {
+  #[inline]
+  fn write_str(&mut self, s: &str) -> FmtResult {
+    if self.put(s.as_bytes()) < s.len() {
+      Err(core::fmt::Error)
+    } else {
+      Ok(())
+    }
   }
 }
 
+#[cfg(feature = "std")]
 impl io::Write for Writer<'_> {
   #[inline]
   fn write(&mut self, data: &[u8]) -> io::Result<usize> {
-    let len = min(data.len(), self.buffer.len() - self.written);
-    let ptr = self.buffer[self.written..].as_mut_ptr().cast::<u8>();
-    // SAFETY: Both source and destination are valid for reads and are
-    //         properly aligned as they originate from references. They
-    //         cannot overlap because this method has exclusive access
-    //         to the buffer we write to.
-    let () = unsafe { ptr.copy_from_nonoverlapping(data.as_ptr(), len) };
+    if data.len() > self.remaining() && self.overflow == Overflow::Error {
+      self.overflowed = true;
+      return Err(io::Error::new(
+        io::ErrorKind::WriteZero,
+        Overflowed {
+          dropped: data.len() - self.remaining(),
+        },
+      ))
+    }
+    Ok(self.put(data))
+  }
 
-    self.written += len;
-    Ok(len)
+  #[inline]
+  fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    let mut total = 0;
+    for buf in bufs {
+      let n = self.write(buf)?;
+      total += n;
+      // Once a fragment no longer fits completely the buffer is
+      // exhausted and there is no point in attempting the rest.
+      if n < buf.len() {
+        break
+      }
+    }
+    Ok(total)
+  }
+
+  #[cfg(feature = "nightly")]
+  #[inline]
+  fn is_write_vectored(&self) -> bool {
+    true
   }
 
   #[inline]
@@ -82,7 +342,7 @@ impl io::Write for Writer<'_> {
 }
 
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
   use super::*;
 
@@ -125,4 +385,127 @@ mod tests {
       [b'4', b'5', b'6', b'1', b'2', b'3', b'4', b'5']
     );
   }
+
+  /// Check that strict overflow handling reports truncation as an error.
+  #[test]
+  fn strict_overflow() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 4];
+    let mut writer = Writer::new_strict(&mut buffer);
+
+    assert_eq!(writer.remaining(), 4);
+    assert!(!writer.overflowed());
+
+    let n = writer.write(b"ab").unwrap();
+    assert_eq!(n, 2);
+    assert_eq!(writer.remaining(), 2);
+    assert!(!writer.overflowed());
+
+    let err = writer.write(b"cde").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    let overflowed = err.into_inner().unwrap().downcast::<Overflowed>().unwrap();
+    assert_eq!(overflowed.dropped, 1);
+    assert!(writer.overflowed());
+    // The rejected write must not have touched the buffer.
+    assert_eq!(writer.written(), [b'a', b'b']);
+
+    let () = writer.reset();
+    assert!(!writer.overflowed());
+    assert_eq!(writer.remaining(), 4);
+  }
+
+  /// Check that [`Writer::fill_from`] drains a reader into the tail.
+  #[test]
+  fn fill_from_reader() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 8];
+    let mut writer = Writer::new(&mut buffer);
+
+    let n = writer.write(b"ab").unwrap();
+    assert_eq!(n, 2);
+
+    let mut reader = &b"cdefghij"[..];
+    let n = writer.fill_from(&mut reader).unwrap();
+    // Only the remaining six bytes fit.
+    assert_eq!(n, 6);
+    assert_eq!(writer.written(), b"abcdefgh");
+    assert_eq!(writer.remaining(), 0);
+
+    // A subsequent fill on a full buffer is a no-op.
+    let mut reader = &b"kl"[..];
+    let n = writer.fill_from(&mut reader).unwrap();
+    assert_eq!(n, 0);
+  }
+
+  /// Check that [`Writer::fill_from`] stops cleanly at reader EOF.
+  #[test]
+  fn fill_from_short_reader() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 8];
+    let mut writer = Writer::new(&mut buffer);
+
+    let mut reader = &b"xyz"[..];
+    let n = writer.fill_from(&mut reader).unwrap();
+    assert_eq!(n, 3);
+    assert_eq!(writer.written(), b"xyz");
+    assert_eq!(writer.remaining(), 5);
+  }
+
+  /// Check that vectored writes copy successive slices into the tail.
+  #[test]
+  fn vectored_writing() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 8];
+    let mut writer = Writer::new(&mut buffer);
+
+    #[cfg(feature = "nightly")]
+    assert!(writer.is_write_vectored());
+
+    let bufs = [IoSlice::new(b"foo"), IoSlice::new(b"bar")];
+    let n = writer.write_vectored(&bufs).unwrap();
+    assert_eq!(n, 6);
+    assert_eq!(writer.written(), b"foobar");
+
+    // The first slice fills the buffer exactly; the second is dropped.
+    let bufs = [IoSlice::new(b"ba"), IoSlice::new(b"z")];
+    let n = writer.write_vectored(&bufs).unwrap();
+    assert_eq!(n, 2);
+    assert_eq!(writer.written(), b"foobarba");
+  }
+
+  /// Check that the spare capacity can be filled and committed manually.
+  #[test]
+  fn spare_capacity_commit() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 8];
+    let mut writer = Writer::new(&mut buffer);
+
+    let spare = writer.spare_capacity();
+    assert_eq!(spare.len(), 8);
+    spare[0].write(b'h');
+    spare[1].write(b'i');
+    // SAFETY: We just initialized the first two bytes of the tail.
+    let () = unsafe { writer.advance(2) };
+
+    assert_eq!(writer.written(), b"hi");
+    assert_eq!(writer.remaining(), 6);
+  }
+}
+
+
+#[cfg(all(test, not(feature = "std")))]
+mod fmt_tests {
+  use super::*;
+
+
+  /// Check that the `core::fmt::Write` impl formats into the buffer and
+  /// reports overflow as a formatting error.
+  #[test]
+  fn fmt_writing() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 8];
+    let mut writer = Writer::new(&mut buffer);
+
+    let () = writer.write_str("539").unwrap();
+    assert_eq!(writer.written(), b"539");
+    assert!(!writer.overflowed());
+
+    // Overflowing formatting surfaces as a `fmt::Error`.
+    let _err = writer.write_str("abcdefgh").unwrap_err();
+    assert!(writer.overflowed());
+  }
 }